@@ -0,0 +1,427 @@
+use syn::meta::ParseNestedMeta;
+use syn::spanned::Spanned;
+
+/// Options of `#[builder(setter(...))]`, usable on the struct as a default
+/// for every field and on a single field as an override.
+#[derive(Default)]
+pub(crate) struct SetterAttr {
+    pub into: Option<bool>,
+    pub strip_option: Option<bool>,
+    pub name: Option<syn::Ident>,
+    pub prefix: Option<String>,
+}
+
+/// `#[builder(preset(name = "...", fields(field = value, ...)))]`, a
+/// constructor of the builder with some fields already set.
+pub(crate) struct Preset {
+    pub name: syn::Ident,
+    pub fields: Vec<(syn::Ident, syn::Expr)>,
+}
+
+#[derive(Default)]
+pub(crate) struct StructAttr {
+    pub setter: SetterAttr,
+    pub validate: Option<syn::Path>,
+    pub vis: Option<syn::Visibility>,
+    pub getters: bool,
+    pub try_setter: bool,
+    pub derive: Vec<syn::Path>,
+    pub no_std: bool,
+    pub patch: bool,
+    pub name: Option<syn::Ident>,
+    pub build_fn_name: Option<syn::Ident>,
+    pub presets: Vec<Preset>,
+}
+
+/// `#[builder(sub_builder(ty = "...", build_fn = "..."))]`, with both
+/// defaulting to those a derived `Builder` has.
+#[derive(Default)]
+pub(crate) struct SubBuilderAttr {
+    pub ty: Option<syn::Type>,
+    pub build_fn: Option<syn::Ident>,
+}
+
+#[derive(Default)]
+pub(crate) struct FieldAttr {
+    pub name: Option<syn::Ident>,
+    pub each: Option<syn::Ident>,
+    pub each_extend: Option<syn::Ident>,
+    pub default: Option<syn::Expr>,
+    pub skip: Option<syn::Expr>,
+    pub env: Option<syn::LitStr>,
+    pub sub_builder: Option<SubBuilderAttr>,
+    pub optional: Option<bool>,
+    pub vis: Option<syn::Visibility>,
+    pub try_setter: Option<bool>,
+    pub setter: SetterAttr,
+}
+
+const STRUCT_KEYS: &[(&str, &str)] = &[
+    ("setter", "setter(...)"),
+    ("validate", r#"validate = "...""#),
+    ("vis", r#"vis = "...""#),
+    ("getters", "getters"),
+    ("try_setter", "try_setter"),
+    ("derive", "derive(...)"),
+    ("no_std", "no_std"),
+    ("patch", "patch"),
+    ("name", r#"name = "...""#),
+    ("build_fn", "build_fn(...)"),
+    ("preset", "preset(...)"),
+];
+
+const FIELD_KEYS: &[(&str, &str)] = &[
+    ("each", r#"each = "...""#),
+    ("name", r#"name = "...""#),
+    ("default", "default = ..."),
+    ("skip", r#"skip = "...""#),
+    ("env", r#"env = "...""#),
+    ("sub_builder", "sub_builder"),
+    ("optional", "optional"),
+    ("required", "required"),
+    ("vis", r#"vis = "...""#),
+    ("try_setter", "try_setter"),
+    ("setter", "setter(...)"),
+];
+
+const EACH_KEYS: &[(&str, &str)] = &[("name", r#"name = "...""#), ("extend", r#"extend = "...""#)];
+
+const BUILD_FN_KEYS: &[(&str, &str)] = &[("name", r#"name = "...""#)];
+
+const PRESET_KEYS: &[(&str, &str)] = &[("name", r#"name = "...""#), ("fields", "fields(...)")];
+
+/// Keys that may be given more than once, each adding to the previous ones.
+const REPEATABLE_KEYS: &[&str] = &["derive", "preset"];
+
+const SUB_BUILDER_KEYS: &[(&str, &str)] =
+    &[("ty", r#"ty = "...""#), ("build_fn", r#"build_fn = "...""#)];
+
+const SETTER_KEYS: &[(&str, &str)] = &[
+    ("into", "into"),
+    ("strip_option", "strip_option = false"),
+    ("name", r#"name = "...""#),
+    ("prefix", r#"prefix = "...""#),
+];
+
+pub(crate) fn get_struct_attr(st: &syn::DeriveInput) -> syn::Result<StructAttr> {
+    let mut attr = StructAttr::default();
+    parse_builder_attrs(&st.attrs, |meta| {
+        if meta.path.is_ident("setter") {
+            let setter = get_setter_attr(meta)?;
+            if let Some(ref name) = setter.name {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "`setter(name = ...)` is only allowed on fields",
+                ));
+            }
+            attr.setter = setter;
+        } else if meta.path.is_ident("validate") {
+            attr.validate = Some(parse_maybe_quoted(meta)?);
+        } else if meta.path.is_ident("vis") {
+            attr.vis = Some(parse_maybe_quoted(meta)?);
+        } else if meta.path.is_ident("getters") {
+            attr.getters = parse_flag(meta)?;
+        } else if meta.path.is_ident("try_setter") {
+            attr.try_setter = parse_flag(meta)?;
+        } else if meta.path.is_ident("no_std") {
+            attr.no_std = parse_flag(meta)?;
+        } else if meta.path.is_ident("patch") {
+            attr.patch = parse_flag(meta)?;
+        } else if meta.path.is_ident("name") {
+            attr.name = Some(parse_lit_ident(meta)?);
+        } else if meta.path.is_ident("build_fn") {
+            parse_nested_list(meta, |build_fn| {
+                if build_fn.path.is_ident("name") {
+                    attr.build_fn_name = Some(parse_lit_ident(build_fn)?);
+                } else {
+                    return Err(unknown_attr_error(&build_fn.path, BUILD_FN_KEYS));
+                }
+                Ok(())
+            })?;
+        } else if meta.path.is_ident("preset") {
+            attr.presets.push(get_preset(meta)?);
+        } else if meta.path.is_ident("derive") {
+            parse_nested_list(meta, |derive| {
+                // The builder and the patch implement `Default` already.
+                if derive
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|seg| seg.ident == "Default")
+                {
+                    return Err(syn::Error::new_spanned(
+                        &derive.path,
+                        "`Default` is already implemented for the builder",
+                    ));
+                }
+                attr.derive.push(derive.path.clone());
+                Ok(())
+            })?;
+        } else {
+            return Err(unknown_attr_error(&meta.path, STRUCT_KEYS));
+        }
+        Ok(())
+    })?;
+    Ok(attr)
+}
+
+pub(crate) fn get_field_attr(field: &syn::Field) -> syn::Result<FieldAttr> {
+    let mut attr = FieldAttr::default();
+    parse_builder_attrs(&field.attrs, |meta| {
+        if meta.path.is_ident("each") {
+            // `each(name = "...", extend = "...")` also names the setter
+            // taking many items at once.
+            if meta.input.peek(syn::token::Paren) {
+                parse_nested_list(meta, |each| {
+                    if each.path.is_ident("name") {
+                        attr.each = Some(parse_lit_ident(each)?);
+                    } else if each.path.is_ident("extend") {
+                        attr.each_extend = Some(parse_lit_ident(each)?);
+                    } else {
+                        return Err(unknown_attr_error(&each.path, EACH_KEYS));
+                    }
+                    Ok(())
+                })?;
+                if attr.each.is_none() {
+                    return Err(meta.error("`each(...)` requires `name = \"...\"`"));
+                }
+            } else {
+                attr.each = Some(parse_lit_ident(meta)?);
+            }
+        } else if meta.path.is_ident("name") {
+            attr.name = Some(parse_lit_ident(meta)?);
+        } else if meta.path.is_ident("default") {
+            // A bare `default` falls back to `Default::default()`.
+            attr.default = Some(if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse()?
+            } else {
+                syn::parse_quote_spanned!(field.ty.span()=> ::core::default::Default::default())
+            });
+        } else if meta.path.is_ident("env") {
+            attr.env = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("optional") || meta.path.is_ident("required") {
+            if attr.optional.is_some() {
+                return Err(meta.error("field is already marked `optional` or `required`"));
+            }
+            attr.optional = Some(meta.path.is_ident("optional"));
+        } else if meta.path.is_ident("sub_builder") {
+            // The builder type can be named in case it is not the default
+            // `{Type}Builder`, and so can its build function with the list
+            // form, in case it is not `build`.
+            let mut sub_builder = SubBuilderAttr::default();
+            if meta.input.peek(syn::Token![=]) {
+                sub_builder.ty = Some(parse_maybe_quoted(meta)?);
+            } else if meta.input.peek(syn::token::Paren) {
+                parse_nested_list(meta, |item| {
+                    if item.path.is_ident("ty") {
+                        sub_builder.ty = Some(parse_maybe_quoted(item)?);
+                    } else if item.path.is_ident("build_fn") {
+                        sub_builder.build_fn = Some(parse_lit_ident(item)?);
+                    } else {
+                        return Err(unknown_attr_error(&item.path, SUB_BUILDER_KEYS));
+                    }
+                    Ok(())
+                })?;
+            }
+            attr.sub_builder = Some(sub_builder);
+        } else if meta.path.is_ident("skip") {
+            // A bare `skip` initializes the field with `Default::default()`.
+            attr.skip = Some(if meta.input.peek(syn::Token![=]) {
+                parse_maybe_quoted(meta)?
+            } else {
+                syn::parse_quote_spanned!(field.ty.span()=> ::core::default::Default::default())
+            });
+        } else if meta.path.is_ident("vis") {
+            attr.vis = Some(parse_maybe_quoted(meta)?);
+        } else if meta.path.is_ident("try_setter") {
+            attr.try_setter = Some(parse_flag(meta)?);
+        } else if meta.path.is_ident("setter") {
+            attr.setter = get_setter_attr(meta)?;
+        } else {
+            return Err(unknown_attr_error(&meta.path, FIELD_KEYS));
+        }
+        Ok(())
+    })?;
+    Ok(attr)
+}
+
+fn get_setter_attr(setter: &ParseNestedMeta) -> syn::Result<SetterAttr> {
+    let mut attr = SetterAttr::default();
+    parse_nested_list(setter, |meta| {
+        if meta.path.is_ident("into") {
+            attr.into = Some(parse_flag(meta)?);
+        } else if meta.path.is_ident("strip_option") {
+            attr.strip_option = Some(parse_flag(meta)?);
+        } else if meta.path.is_ident("name") {
+            attr.name = Some(parse_lit_ident(meta)?);
+        } else if meta.path.is_ident("prefix") {
+            attr.prefix = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+        } else {
+            return Err(unknown_attr_error(&meta.path, SETTER_KEYS));
+        }
+        Ok(())
+    })?;
+    Ok(attr)
+}
+
+fn get_preset(preset: &ParseNestedMeta) -> syn::Result<Preset> {
+    let mut name = None;
+    let mut fields = Vec::new();
+    parse_nested_list(preset, |meta| {
+        if meta.path.is_ident("name") {
+            name = Some(parse_lit_ident(meta)?);
+        } else if meta.path.is_ident("fields") {
+            parse_nested_list(meta, |field| {
+                let ident = field.path.require_ident()?.clone();
+                fields.push((ident, field.value()?.parse()?));
+                Ok(())
+            })?;
+        } else {
+            return Err(unknown_attr_error(&meta.path, PRESET_KEYS));
+        }
+        Ok(())
+    })?;
+    match name {
+        Some(name) => Ok(Preset { name, fields }),
+        None => Err(preset.error("`preset(...)` requires `name = \"...\"`")),
+    }
+}
+
+/// Runs `parse_item` on the items of every `#[builder(...)]` in `attrs`,
+/// collecting the errors of all of them instead of stopping at the first.
+fn parse_builder_attrs(
+    attrs: &[syn::Attribute],
+    mut parse_item: impl FnMut(&ParseNestedMeta) -> syn::Result<()>,
+) -> syn::Result<()> {
+    let mut errors = None;
+    let mut seen = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("builder")) {
+        if let Err(e) = attr.parse_nested_meta(|meta| {
+            parse_item_or_skip(&meta, &mut seen, &mut errors, &mut parse_item)
+        }) {
+            push_error(&mut errors, e);
+        }
+    }
+    errors.map_or(Ok(()), Err)
+}
+
+/// Like `parse_builder_attrs` for a nested list such as `setter(...)`.
+fn parse_nested_list(
+    list: &ParseNestedMeta,
+    mut parse_item: impl FnMut(&ParseNestedMeta) -> syn::Result<()>,
+) -> syn::Result<()> {
+    let mut errors = None;
+    let mut seen = Vec::new();
+    if let Err(e) = list.parse_nested_meta(|meta| {
+        parse_item_or_skip(&meta, &mut seen, &mut errors, &mut parse_item)
+    }) {
+        push_error(&mut errors, e);
+    }
+    errors.map_or(Ok(()), Err)
+}
+
+/// Records the error of an item that failed to parse and skips the rest of it,
+/// up to the comma before the next item. Keys other than `REPEATABLE_KEYS`
+/// already in `seen` are rejected.
+fn parse_item_or_skip(
+    meta: &ParseNestedMeta,
+    seen: &mut Vec<String>,
+    errors: &mut Option<syn::Error>,
+    parse_item: &mut impl FnMut(&ParseNestedMeta) -> syn::Result<()>,
+) -> syn::Result<()> {
+    let path = &meta.path;
+    let key = quote::quote!(#path).to_string();
+    let result = if seen.contains(&key) && !REPEATABLE_KEYS.contains(&key.as_str()) {
+        Err(syn::Error::new_spanned(
+            path,
+            format!("duplicate builder attribute `{}`", key),
+        ))
+    } else {
+        seen.push(key);
+        parse_item(meta)
+    };
+    if let Err(e) = result {
+        push_error(errors, e);
+        while !meta.input.is_empty() && !meta.input.peek(syn::Token![,]) {
+            meta.input.parse::<proc_macro2::TokenTree>()?;
+        }
+    }
+    Ok(())
+}
+
+/// Parses `key = "ident"`.
+fn parse_lit_ident(meta: &ParseNestedMeta) -> syn::Result<syn::Ident> {
+    meta.value()?.parse::<syn::LitStr>()?.parse()
+}
+
+/// Parses `key = "value"` as well as `key = value`, such as both
+/// `vis = "pub(crate)"` and `vis = pub(crate)`.
+fn parse_maybe_quoted<T: syn::parse::Parse>(meta: &ParseNestedMeta) -> syn::Result<T> {
+    let value = meta.value()?;
+    if value.peek(syn::LitStr) {
+        value.parse::<syn::LitStr>()?.parse()
+    } else {
+        value.parse()
+    }
+}
+
+/// Parses a bare `key`, meaning `true`, or `key = true` / `key = false`.
+fn parse_flag(meta: &ParseNestedMeta) -> syn::Result<bool> {
+    if meta.input.peek(syn::Token![=]) {
+        Ok(meta.value()?.parse::<syn::LitBool>()?.value)
+    } else {
+        Ok(true)
+    }
+}
+
+pub(crate) fn push_error(errors: &mut Option<syn::Error>, e: syn::Error) {
+    match errors {
+        Some(errors) => errors.combine(e),
+        None => *errors = Some(e),
+    }
+}
+
+/// Reports a key of `#[builder(...)]` that is not one of `keys`, each given
+/// along with the syntax it expects. Misspelled keys get a suggestion.
+fn unknown_attr_error(path: &syn::Path, keys: &[(&str, &str)]) -> syn::Error {
+    let expected = keys
+        .iter()
+        .map(|(_, usage)| format!("`{}`", usage))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let name = match path.get_ident() {
+        Some(ident) => ident.to_string(),
+        None => return syn::Error::new_spanned(path, format!("expected one of {}", expected)),
+    };
+    let suggestion = keys
+        .iter()
+        .map(|(key, _)| (edit_distance(key, &name), key))
+        .filter(|(distance, key)| *distance <= 2 && *distance < key.len())
+        .min();
+    let msg = match suggestion {
+        Some((_, key)) => format!(
+            "unknown builder attribute `{}`, did you mean `{}`?",
+            name, key
+        ),
+        None => format!(
+            "unknown builder attribute `{}`, expected one of {}",
+            name, expected
+        ),
+    };
+    syn::Error::new_spanned(path, msg)
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitute = prev[j] + usize::from(ca != *cb);
+            cur.push(substitute.min(prev[j + 1] + 1).min(cur[j] + 1));
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
@@ -1,6 +1,8 @@
+mod attr;
+
+use attr::{get_field_attr, get_struct_attr, push_error, StructAttr};
 use proc_macro::TokenStream;
-use proc_macro2;
-use quote::quote;
+use quote::{format_ident, quote, quote_spanned};
 use syn::{parse_macro_input, spanned::Spanned, DeriveInput};
 
 #[proc_macro_derive(Builder, attributes(builder))]
@@ -14,179 +16,1010 @@ pub fn derive(input: TokenStream) -> TokenStream {
 
 fn expand(st: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let struct_ident = &st.ident;
-    let fields = get_struct_fields(st)?;
-    let builder_struct_ident =
-        syn::Ident::new(&format!("{}Builder", struct_ident.to_string()), st.span());
+    // Keep going after a malformed struct attribute so that the errors of
+    // every field are reported along with it.
+    let (struct_attr, errors) = match get_struct_attr(st) {
+        Ok(attr) => (attr, None),
+        Err(e) => (StructAttr::default(), Some(e)),
+    };
+    let ret = match st.data {
+        syn::Data::Struct(syn::DataStruct { ref fields, .. }) => {
+            let builder_struct_ident = match struct_attr.name {
+                Some(ref name) => name.clone(),
+                None => format_ident!("{}Builder", struct_ident, span = struct_ident.span()),
+            };
+            expand_builder(
+                st,
+                &struct_attr,
+                &quote!(#struct_ident),
+                fields,
+                &builder_struct_ident,
+                &format_ident!("builder"),
+            )
+        }
+        // Every variant gets its own builder, e.g. `Shape::circle_builder()`
+        // returning a `ShapeCircleBuilder` that builds a `Shape::Circle`.
+        syn::Data::Enum(syn::DataEnum { ref variants, .. }) => {
+            let mut ret = proc_macro2::TokenStream::new();
+            let mut errors = None;
+            if let Some(ref name) = struct_attr.name {
+                push_error(
+                    &mut errors,
+                    syn::Error::new_spanned(name, "`name` is only allowed on structs"),
+                );
+            }
+            for preset in struct_attr.presets.iter() {
+                push_error(
+                    &mut errors,
+                    syn::Error::new_spanned(&preset.name, "`preset` is only allowed on structs"),
+                );
+            }
+            for variant in variants.iter() {
+                // Builder options are given on the enum and its fields.
+                for attr in variant.attrs.iter() {
+                    if attr.path().is_ident("builder") {
+                        push_error(
+                            &mut errors,
+                            syn::Error::new_spanned(
+                                attr,
+                                "`#[builder(...)]` is not allowed on enum variants",
+                            ),
+                        );
+                    }
+                }
+                let variant_ident = &variant.ident;
+                let builder_struct_ident = format_ident!(
+                    "{}{}Builder",
+                    struct_ident,
+                    variant_ident,
+                    span = variant_ident.span()
+                );
+                let builder_fn_ident = format_ident!(
+                    "{}_builder",
+                    to_snake_case(&variant_ident.to_string()),
+                    span = variant_ident.span()
+                );
+                match expand_builder(
+                    st,
+                    &struct_attr,
+                    &quote!(#struct_ident::#variant_ident),
+                    &variant.fields,
+                    &builder_struct_ident,
+                    &builder_fn_ident,
+                ) {
+                    Ok(builder) => ret.extend(builder),
+                    Err(e) => push_error(&mut errors, e),
+                }
+            }
+            errors.map_or(Ok(ret), Err)
+        }
+        syn::Data::Union(_) => Err(syn::Error::new_spanned(
+            st,
+            "`Builder` can only be derived for structs and enums",
+        )),
+    };
+    match (ret, errors) {
+        (Ok(ret), None) => Ok(ret),
+        (Ok(_), Some(errors)) => Err(errors),
+        (Err(e), Some(mut errors)) => {
+            errors.combine(e);
+            Err(errors)
+        }
+        (Err(e), None) => Err(e),
+    }
+}
 
+/// Generates a builder for one set of fields: those of a struct, or those of
+/// one variant of an enum. `target` is the path the fields are assembled into
+/// by the build function, and `builder_fn_ident` names the associated function that
+/// creates the builder.
+fn expand_builder(
+    st: &DeriveInput,
+    struct_attr: &StructAttr,
+    target: &proc_macro2::TokenStream,
+    fields: &syn::Fields,
+    builder_struct_ident: &syn::Ident,
+    builder_fn_ident: &syn::Ident,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let struct_ident = &st.ident;
     let mut builder_struct_content = proc_macro2::TokenStream::new();
     let mut builder_fn_content = proc_macro2::TokenStream::new();
     let mut builder_setters = proc_macro2::TokenStream::new();
     let mut check_field_is_none = proc_macro2::TokenStream::new();
     let mut builder_to_struct_content = proc_macro2::TokenStream::new();
-    for f in fields.iter() {
-        let ident = &f.ident;
+    let mut builder_from_struct_content = proc_macro2::TokenStream::new();
+    let mut to_builder_content = proc_macro2::TokenStream::new();
+    let mut patch_struct_content = proc_macro2::TokenStream::new();
+    let mut patch_default_content = proc_macro2::TokenStream::new();
+    let mut merge_content = proc_macro2::TokenStream::new();
+    // Only the error type and `format!` need an allocator, everything else
+    // comes from `core`.
+    let (error_ty, format) = if struct_attr.no_std {
+        (
+            quote!(::alloc::boxed::Box<dyn ::core::error::Error>),
+            quote!(::alloc::format!),
+        )
+    } else {
+        (
+            quote!(::std::boxed::Box<dyn ::std::error::Error>),
+            quote!(::std::format!),
+        )
+    };
+    let mut required_fields = Vec::new();
+    let mut required_names = Vec::new();
+    let mut sub_builder_names = Vec::new();
+    let mut env_vars = Vec::new();
+    // The builder fields a preset can set, along with whether they hold a
+    // sub-builder and whether their setter converts the value with `Into`.
+    let mut preset_fields = Vec::new();
+    // Every method generated for a field along with the field, to report
+    // fields whose setters end up with the same name.
+    let mut field_methods = Vec::new();
+    let mut errors = None;
+    // The builder is as visible as the struct it builds unless overridden
+    // with `#[builder(vis = "...")]`, and so are its setters unless a field
+    // overrides them in turn.
+    let vis = struct_attr.vis.as_ref().unwrap_or(&st.vis);
+    for (i, f) in fields.iter().enumerate() {
+        let attr = match get_field_attr(f) {
+            Ok(attr) => attr,
+            Err(e) => {
+                push_error(&mut errors, e);
+                continue;
+            }
+        };
+        // Only the tokens carrying a trait obligation, such as the `clone`
+        // calls and the trait paths below, are spanned at the field, so that
+        // a type lacking the trait is reported at it. Everything else keeps
+        // the call site span, as the field may have been written in another
+        // macro context than the derive.
+        let span = f.ty.span();
+        let clone = format_ident!("clone", span = span);
+        let default_fn = quote_spanned!(span=> ::core::default::Default::default);
+        let option_ty = quote_spanned!(span=> ::core::option::Option);
+        let member = match f.ident {
+            Some(ref ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(syn::Index {
+                index: i as u32,
+                span,
+            }),
+        };
+        // Skipped fields have neither a builder field nor a setter, they are
+        // only initialized by the build function.
+        if let Some(ref skip) = attr.skip {
+            let setter = &attr.setter;
+            let ignored: Vec<_> = [
+                ("each", attr.each.is_some()),
+                ("name", attr.name.is_some()),
+                ("default", attr.default.is_some()),
+                ("env", attr.env.is_some()),
+                ("sub_builder", attr.sub_builder.is_some()),
+                ("optional", attr.optional == Some(true)),
+                ("required", attr.optional == Some(false)),
+                ("vis", attr.vis.is_some()),
+                ("try_setter", attr.try_setter.is_some()),
+                (
+                    "setter",
+                    setter.into.is_some()
+                        || setter.strip_option.is_some()
+                        || setter.name.is_some()
+                        || setter.prefix.is_some(),
+                ),
+            ]
+            .iter()
+            .filter(|(_, given)| *given)
+            .map(|(key, _)| format!("`{}`", key))
+            .collect();
+            if !ignored.is_empty() {
+                push_error(
+                    &mut errors,
+                    syn::Error::new(
+                        member.span(),
+                        format!(
+                            "`skip` cannot be combined with {}, a skipped field has no setter",
+                            ignored.join(", ")
+                        ),
+                    ),
+                );
+            }
+            builder_to_struct_content.extend(quote!(
+            #member: #skip,
+            ));
+            continue;
+        }
+        // The name of the field inside the builder, from which the setter
+        // name is derived as well.
+        let ident = &match (&f.ident, &attr.name) {
+            (Some(ident), None) => ident.clone(),
+            (None, Some(name)) => name.clone(),
+            (None, None) => format_ident!("_{}", i, span = f.ty.span()),
+            (Some(_), Some(name)) => {
+                push_error(
+                    &mut errors,
+                    syn::Error::new_spanned(
+                        name,
+                        "`name` is only allowed on tuple struct fields, use `setter(name = ...)`",
+                    ),
+                );
+                continue;
+            }
+        };
         let ty = &f.ty;
-        let inner_ty = match get_field_inner_type(ty, "Option".to_string()) {
-            Some(inner_ty) => inner_ty,
-            None => ty,
+        // The documentation of a field is shown on each of its setters.
+        let docs: Vec<_> = f
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("doc"))
+            .collect();
+        // `#[builder(optional)]` also covers aliases of `Option<T>`, whose `T`
+        // can only be named through `IntoIterator`. Any other type is caught
+        // by a conversion spanned at the field, which only compiles for
+        // `Option<T>`.
+        let alias_inner_ty: syn::Type;
+        let inner_ty = match (attr.optional, get_option_inner_type(ty)) {
+            (Some(false), _) => ty,
+            (_, Some(inner_ty)) => inner_ty,
+            (Some(true), None) => {
+                alias_inner_ty = syn::parse_quote!(<#ty as ::core::iter::IntoIterator>::Item);
+                check_field_is_none.extend(quote_spanned!(ty.span()=>
+                let _: fn(#ty) -> ::core::option::Option<#alias_inner_ty> = |value| value;
+                ));
+                &alias_inner_ty
+            }
+            (None, None) => ty,
+        };
+        // Without type arguments to read the item type from, such as for an
+        // alias of a collection, the item is named through `IntoIterator`.
+        let alias_item_ty: syn::Type;
+        let item_tys = match get_collection_item_types(inner_ty) {
+            Some(item_tys) => Some(item_tys),
+            None if matches!(ungroup(inner_ty), syn::Type::Path(_)) => {
+                alias_item_ty = syn::parse_quote!(<#inner_ty as ::core::iter::IntoIterator>::Item);
+                Some(vec![&alias_item_ty])
+            }
+            None => None,
+        };
+        let each = match attr.each {
+            Some(ref each) => match item_tys {
+                Some(item_tys) => {
+                    let extend = attr
+                        .each_extend
+                        .clone()
+                        .unwrap_or_else(|| format_ident!("{}_extend", ident, span = each.span()));
+                    Some((each, extend, item_tys))
+                }
+                None => {
+                    push_error(
+                        &mut errors,
+                        syn::Error::new_spanned(
+                            ty,
+                            "`each` requires a collection type such as `Vec<T>` or `HashMap<K, V>`",
+                        ),
+                    );
+                    continue;
+                }
+            },
+            None => None,
         };
-        let inner_vec_ty = match get_field_inner_type(inner_ty, "Vec".to_string()) {
-            Some(inner_vec_ty) => inner_vec_ty,
-            None => inner_ty,
+        let setter_vis = attr.vis.as_ref().unwrap_or(vis);
+        let into = attr
+            .setter
+            .into
+            .or(struct_attr.setter.into)
+            .unwrap_or(false);
+        let strip_option = attr
+            .setter
+            .strip_option
+            .or(struct_attr.setter.strip_option)
+            .unwrap_or(true);
+        let prefix = attr
+            .setter
+            .prefix
+            .as_ref()
+            .or(struct_attr.setter.prefix.as_ref());
+        let setter_ident = match (&attr.setter.name, prefix) {
+            (Some(name), _) => name.clone(),
+            (None, Some(prefix)) => format_ident!("{}{}", prefix, ident, span = ident.span()),
+            (None, None) => ident.clone(),
         };
-        let attr = get_attr_builder_name(f)?;
+
+        // A sub-builder is stored and exposed as is, and only built along
+        // with the outer builder.
+        if let Some(ref sub_builder) = attr.sub_builder {
+            let build_fn = match sub_builder.build_fn {
+                Some(ref build_fn) => build_fn.clone(),
+                None => format_ident!("build"),
+            };
+            let builder_ty = match sub_builder.ty.clone().or_else(|| get_sub_builder_type(ty)) {
+                Some(builder_ty)
+                    if ty == inner_ty
+                        && each.is_none()
+                        && attr.default.is_none()
+                        && attr.env.is_none() =>
+                {
+                    builder_ty
+                }
+                _ => {
+                    push_error(
+                        &mut errors,
+                        syn::Error::new_spanned(
+                            ty,
+                            "`sub_builder` requires a non-optional field of a type deriving `Builder`, \
+                             and cannot be combined with `each`, `default` or `env`",
+                        ),
+                    );
+                    continue;
+                }
+            };
+            builder_fn_content.extend(quote!(
+            #ident: ::core::default::Default::default(),
+            ));
+            patch_default_content.extend(quote!(
+            #ident: ::core::option::Option::None,
+            ));
+            builder_struct_content.extend(quote!(
+            #ident: #builder_ty,
+            ));
+            field_methods.push((setter_ident.clone(), ident.clone()));
+            builder_setters.extend(quote!(
+            #(#docs)*
+            #setter_vis fn #setter_ident(&mut self) -> &mut #builder_ty {
+                &mut self.#ident
+            }
+            ));
+            builder_from_struct_content.extend(quote!(
+            #ident: ::core::convert::From::from(value.#member),
+            ));
+            to_builder_content.extend(quote!(
+            #ident: self.#member.to_builder(),
+            ));
+            // A patch replaces the whole sub-builder.
+            patch_struct_content.extend(quote!(
+            #vis #ident: ::core::option::Option<#ty>,
+            ));
+            merge_content.extend(quote!(
+            if let ::core::option::Option::Some(value) = patch.#ident {
+                self.#ident = ::core::convert::From::from(value);
+            }
+            ));
+            required_fields.push(quote!(self.#ident.is_complete()));
+            sub_builder_names.push(format!("`{}`", ident));
+            preset_fields.push((ident.clone(), true, false));
+            builder_to_struct_content.extend(quote!(
+            #member: match self.#ident.#build_fn() {
+                ::core::result::Result::Ok(value) => value,
+                ::core::result::Result::Err(err) => {
+                    let err = #format("{}.{}", ::core::stringify!(#ident), err);
+                    return ::core::result::Result::Err(::core::convert::Into::into(err));
+                }
+            },
+            ));
+            continue;
+        }
 
         builder_fn_content.extend(quote!(
-        #ident: std::option::Option::None,
+        #ident: ::core::option::Option::None,
+        ));
+        preset_fields.push((ident.clone(), false, into));
+        patch_default_content.extend(quote!(
+        #ident: ::core::option::Option::None,
+        ));
+        builder_struct_content.extend(quote_spanned!(span=>
+        #ident: #option_ty<#inner_ty>,
         ));
-        builder_struct_content.extend(quote!(
-        #ident: std::option::Option<#inner_ty>,
+        patch_struct_content.extend(quote!(
+        #vis #ident: ::core::option::Option<#inner_ty>,
         ));
-        if inner_ty == inner_vec_ty || attr.is_none() {
+        merge_content.extend(quote!(
+        if let ::core::option::Option::Some(value) = patch.#ident {
+            self.#ident = ::core::option::Option::Some(value);
+        }
+        ));
+        // Getters are prefixed with `get_` as the plain field name is taken by
+        // the setter.
+        if struct_attr.getters {
+            let getter_ident = format_ident!("get_{}", ident, span = ident.span());
+            field_methods.push((getter_ident.clone(), ident.clone()));
+            let getter_doc = format!("Returns the value set for `{}`, if any.", ident);
             builder_setters.extend(quote!(
-            fn #ident(&mut self, #ident: #inner_ty) -> &mut Self {
-                self.#ident = Some(#ident);
-                self
+            #[doc = #getter_doc]
+            #setter_vis fn #getter_ident(&self) -> ::core::option::Option<&#inner_ty> {
+                self.#ident.as_ref()
             }
             ));
+        }
+        if ty == inner_ty {
+            builder_from_struct_content.extend(quote!(
+            #ident: ::core::option::Option::Some(value.#member),
+            ));
+            to_builder_content.extend(quote!(
+            #ident: ::core::option::Option::Some(self.#member.#clone()),
+            ));
         } else {
-            let attr_ident = &attr.unwrap();
-            if ident.as_ref() != Some(attr_ident) {
+            builder_from_struct_content.extend(quote!(
+            #ident: value.#member,
+            ));
+            to_builder_content.extend(quote!(
+            #ident: self.#member.#clone(),
+            ));
+        }
+
+        // The plain setter gives way to an `each` setter of the same name.
+        if each
+            .as_ref()
+            .is_none_or(|(each, extend, _)| setter_ident != **each && setter_ident != *extend)
+        {
+            field_methods.push((setter_ident.clone(), ident.clone()));
+            if ty != inner_ty && !strip_option {
+                let (arg_ty, arg) = setter_arg(ident, ty, into);
                 builder_setters.extend(quote!(
-                fn #ident(&mut self, #ident: #inner_ty) -> &mut Self {
-                    self.#ident = Some(#ident);
+                #(#docs)*
+                #setter_vis fn #setter_ident(&mut self, #ident: #arg_ty) -> &mut Self {
+                    self.#ident = #arg;
+                    self
+                }
+                ));
+            } else {
+                let (arg_ty, arg) = setter_arg(ident, inner_ty, into);
+                builder_setters.extend(quote!(
+                #(#docs)*
+                #setter_vis fn #setter_ident(&mut self, #ident: #arg_ty) -> &mut Self {
+                    self.#ident = ::core::option::Option::Some(#arg);
                     self
                 }
                 ));
             }
-            builder_setters.extend(quote!(
-            fn #attr_ident(&mut self, #attr_ident: #inner_vec_ty) -> &mut Self {
-                if let Some(ref mut v) = self.#ident {
-                    v.push(#attr_ident);
+            if attr.try_setter.unwrap_or(struct_attr.try_setter) {
+                let setter_ty = if ty != inner_ty && !strip_option {
+                    ty
                 } else {
-                    self.#ident = std::option::Option::Some(vec![#attr_ident]);
+                    inner_ty
+                };
+                let try_setter_ident =
+                    format_ident!("try_{}", setter_ident, span = setter_ident.span());
+                field_methods.push((try_setter_ident.clone(), ident.clone()));
+                builder_setters.extend(quote!(
+                #(#docs)*
+                #setter_vis fn #try_setter_ident<V: ::core::convert::TryInto<#setter_ty>>(
+                    &mut self,
+                    #ident: V,
+                ) -> ::core::result::Result<&mut Self, <V as ::core::convert::TryInto<#setter_ty>>::Error> {
+                    let value = ::core::convert::TryInto::try_into(#ident)?;
+                    ::core::result::Result::Ok(self.#setter_ident(value))
                 }
+                ));
+            }
+        }
+        if let Some((each, ref extend, ref item_tys)) = each {
+            field_methods.push((each.clone(), ident.clone()));
+            field_methods.push((extend.clone(), ident.clone()));
+            // Maps are extended with `(key, value)` pairs, which the setter
+            // takes as two parameters.
+            let params = if item_tys.len() == 1 {
+                vec![each.clone()]
+            } else {
+                vec![format_ident!("key"), format_ident!("value")]
+            };
+            let (arg_tys, args): (Vec<_>, Vec<_>) = params
+                .iter()
+                .zip(item_tys)
+                .map(|(param, item_ty)| setter_arg(param, item_ty, into))
+                .unzip();
+            let item = if args.len() == 1 {
+                quote!(#(#args)*)
+            } else {
+                quote!((#(#args),*))
+            };
+            let item_ty = if item_tys.len() == 1 {
+                quote!(#(#item_tys)*)
+            } else {
+                quote!((#(#item_tys),*))
+            };
+            // Locals and `self` are spliced into the spanned tokens to keep
+            // their call site span. The braces give the collection argument
+            // the span of the field as well.
+            let collection = quote!(self.#ident.get_or_insert_with(#default_fn));
+            let extend_once = quote_spanned!(span=>
+                ::core::iter::Extend::extend({ #collection }, ::core::iter::once(#item))
+            );
+            let extend_all = quote_spanned!(span=>
+                ::core::iter::Extend::extend({ #collection }, #ident)
+            );
+            builder_setters.extend(quote!(
+            #(#docs)*
+            #setter_vis fn #each(&mut self, #(#params: #arg_tys),*) -> &mut Self {
+                #[allow(unused_braces)]
+                #extend_once;
+                self
+            }
+            #(#docs)*
+            #setter_vis fn #extend(
+                &mut self,
+                #ident: impl ::core::iter::IntoIterator<Item = #item_ty>,
+            ) -> &mut Self {
+                #[allow(unused_braces)]
+                #extend_all;
                 self
             }
             ));
         }
-        if ty == inner_ty {
-            builder_to_struct_content.extend(quote::quote!(
-            #ident: self.#ident.clone().unwrap(),
+        // A field left unset falls back to its environment variable, if that
+        // is set, before falling back to its default.
+        if let Some(ref var) = attr.env {
+            let value = quote!(value);
+            let from_str =
+                quote_spanned!(span=> <#inner_ty as ::core::str::FromStr>::from_str(&#value));
+            if struct_attr.no_std {
+                push_error(
+                    &mut errors,
+                    syn::Error::new_spanned(var, "`env` is not available with `no_std`"),
+                );
+            }
+            if each.is_some() {
+                push_error(
+                    &mut errors,
+                    syn::Error::new_spanned(var, "`env` cannot be combined with `each`"),
+                );
+            }
+            // The field is complete if it is set, or else if the variable
+            // parses, or else if it is unset and the field has a fallback.
+            let has_fallback = attr.default.is_some() || ty != inner_ty;
+            required_fields.push(quote!(
+            (self.#ident.is_some() || match ::std::env::var(#var) {
+                ::core::result::Result::Ok(value) => {
+                    #from_str.is_ok()
+                }
+                ::core::result::Result::Err(_) => #has_fallback,
+            })
+            ));
+            env_vars.push(format!("`{}`", var.value()));
+            check_field_is_none.extend(quote!(
+            if self.#ident.is_none() {
+                if let ::core::result::Result::Ok(value) = ::std::env::var(#var) {
+                    match #from_str {
+                        ::core::result::Result::Ok(value) => {
+                            self.#ident = ::core::option::Option::Some(value);
+                        }
+                        ::core::result::Result::Err(err) => {
+                            let err = #format(
+                                "{} from environment variable {}: {}",
+                                ::core::stringify!(#ident),
+                                #var,
+                                err,
+                            );
+                            return ::core::result::Result::Err(::core::convert::Into::into(err));
+                        }
+                    }
+                }
+            }
+            ));
+        }
+        if let Some(ref default) = attr.default {
+            if ty == inner_ty {
+                builder_to_struct_content.extend(quote!(
+                #member: self.#ident.#clone().unwrap_or_else(|| #default),
+                ));
+            } else {
+                builder_to_struct_content.extend(quote!(
+                #member: self.#ident.#clone().or_else(|| #default),
+                ));
+            }
+        } else if ty == inner_ty {
+            builder_to_struct_content.extend(quote!(
+            #member: self.#ident.#clone().unwrap(),
             ));
-            if inner_ty != inner_vec_ty {
+            if each.is_some() {
                 check_field_is_none.extend(quote!(
                 if self.#ident.is_none() {
-                    self.#ident = std::option::Option::Some(vec![]);
+                    self.#ident = ::core::option::Option::Some(#default_fn());
                 }
                 ));
             } else {
+                match attr.env {
+                    Some(ref var) => required_names.push(format!(
+                        "`{}` (unless `{}` is set)",
+                        ident,
+                        var.value()
+                    )),
+                    None => {
+                        required_fields.push(quote!(self.#ident.is_some()));
+                        required_names.push(format!("`{}`", ident));
+                    }
+                }
                 check_field_is_none.extend(quote!(
                 if self.#ident.is_none() {
-                    let err = format!("{} is None", stringify!(#ident));
-                    return Err(err.into());
+                    let err = #format("{} is None", ::core::stringify!(#ident));
+                    return ::core::result::Result::Err(::core::convert::Into::into(err));
                 }
                 ));
             }
         } else {
-            builder_to_struct_content.extend(quote::quote!(
-            #ident: self.#ident.clone(),
+            builder_to_struct_content.extend(quote!(
+            #member: self.#ident.#clone(),
             ));
         }
     }
 
+    let build_fn_ident = match struct_attr.build_fn_name {
+        Some(ref name) => name.clone(),
+        None => format_ident!("build"),
+    };
+    // Methods of the builder that are not generated for a field, which no
+    // setter may be named like either.
+    let mut builder_methods = vec![
+        (build_fn_ident.clone(), "the build function".to_owned()),
+        (format_ident!("is_complete"), "`is_complete`".to_owned()),
+    ];
+    if struct_attr.patch {
+        builder_methods.push((format_ident!("merge"), "`merge` of `patch`".to_owned()));
+    }
+    for preset in struct_attr.presets.iter() {
+        let name = &preset.name;
+        if let Some((_, other)) = builder_methods.iter().find(|(m, _)| m == name) {
+            push_error(
+                &mut errors,
+                syn::Error::new_spanned(
+                    name,
+                    format!("preset `{}` conflicts with {}", name, other),
+                ),
+            );
+        }
+        builder_methods.push((name.clone(), format!("preset `{}`", name)));
+    }
+    for (i, (method, field)) in field_methods.iter().enumerate() {
+        if let Some((_, other)) = builder_methods.iter().find(|(m, _)| m == method) {
+            let msg = format!(
+                "setter `{}` of field `{}` conflicts with {}",
+                method, field, other
+            );
+            push_error(&mut errors, syn::Error::new(method.span(), msg));
+        } else if let Some((_, other)) = field_methods[..i].iter().find(|(m, _)| m == method) {
+            let msg = if other == field {
+                format!("field `{}` has two setters named `{}`", field, method)
+            } else {
+                format!(
+                    "setter `{}` of field `{}` conflicts with a setter of field `{}`",
+                    method, field, other
+                )
+            };
+            push_error(&mut errors, syn::Error::new(method.span(), msg));
+        }
+    }
+
+    let mut presets = proc_macro2::TokenStream::new();
+    for preset in struct_attr.presets.iter() {
+        let mut preset_content = proc_macro2::TokenStream::new();
+        for (field, value) in preset.fields.iter() {
+            match preset_fields.iter().find(|(ident, ..)| ident == field) {
+                Some((_, true, _)) => preset_content.extend(quote!(
+                builder.#field = ::core::convert::From::from(#value);
+                )),
+                Some((_, false, true)) => preset_content.extend(quote!(
+                builder.#field = ::core::option::Option::Some(::core::convert::Into::into(#value));
+                )),
+                Some((_, false, false)) => preset_content.extend(quote!(
+                builder.#field = ::core::option::Option::Some(#value);
+                )),
+                None => push_error(
+                    &mut errors,
+                    syn::Error::new_spanned(
+                        field,
+                        format!("preset sets unknown builder field `{}`", field),
+                    ),
+                ),
+            }
+        }
+        let preset_ident = &preset.name;
+        let preset_doc = format!(
+            "Creates a [`{}`] with the fields of the `{}` preset already set.",
+            builder_struct_ident, preset_ident
+        );
+        presets.extend(quote!(
+        #[doc = #preset_doc]
+        #vis fn #preset_ident() -> Self {
+            let mut builder: Self = ::core::default::Default::default();
+            #preset_content
+            builder
+        }
+        ));
+    }
+
+    if let Some(e) = errors {
+        return Err(e);
+    }
+
+    let validate = struct_attr.validate.as_ref().map(|validate| {
+        quote!(
+        if let ::core::result::Result::Err(err) = #validate(&value) {
+            return ::core::result::Result::Err(::core::convert::Into::into(err));
+        }
+        )
+    });
+
+    // An enum value may be of any variant, so only structs convert back into
+    // their builder.
+    let from_struct = match st.data {
+        syn::Data::Struct(_) => Some(quote!(
+        impl ::core::convert::From<#struct_ident> for #builder_struct_ident {
+            fn from(value: #struct_ident) -> Self {
+                #builder_struct_ident {
+                    #builder_from_struct_content
+                }
+            }
+        }
+        impl #struct_ident {
+            /// Creates a builder with every field set to the value it has
+            /// in `self`.
+            #vis fn to_builder(&self) -> #builder_struct_ident {
+                #builder_struct_ident {
+                    #to_builder_content
+                }
+            }
+        }
+        )),
+        _ => None,
+    };
+
+    let derives = &struct_attr.derive;
+    let target_name = target.to_string().replace(' ', "");
+    let builder_doc = format!(
+        "Builder for [`{}`], created by [`{}::{}`].",
+        target_name, struct_ident, builder_fn_ident,
+    );
+    let builder_fn_doc = format!(
+        "Creates a [`{}`] with no field set yet.",
+        builder_struct_ident
+    );
+    let mut build_doc = format!(
+        "Builds a [`{}`] from the values set on this builder.",
+        target_name
+    );
+    let mut failures = Vec::new();
+    if !required_names.is_empty() {
+        failures.push(format!(
+            "when any of the required fields {} is unset",
+            required_names.join(", ")
+        ));
+    }
+    if !env_vars.is_empty() {
+        failures.push(format!(
+            "when the environment variable {} of an unset field fails to parse",
+            env_vars.join(", ")
+        ));
+    }
+    if !sub_builder_names.is_empty() {
+        failures.push(format!(
+            "when the sub-builder of {} fails",
+            sub_builder_names.join(", ")
+        ));
+    }
+    if let Some(ref validate) = struct_attr.validate {
+        failures.push(format!(
+            "when `{}` rejects the result",
+            quote!(#validate).to_string().replace(' ', "")
+        ));
+    }
+    if !failures.is_empty() {
+        build_doc.push_str("\n\n# Errors\n\nFails ");
+        build_doc.push_str(&failures.join(", or "));
+        build_doc.push('.');
+    }
+    // With `#[builder(patch)]`, partial values can be collected separately,
+    // e.g. from a config file, and merged into the builder.
+    let patch = if struct_attr.patch {
+        let patch_struct_ident = format_ident!(
+            "{}Patch",
+            target_name.replace("::", ""),
+            span = builder_struct_ident.span()
+        );
+        let patch_doc = format!(
+            "Partial values of [`{}`], merged into a builder with [`{}::merge`].",
+            target_name, builder_struct_ident
+        );
+        Some(quote!(
+        #[doc = #patch_doc]
+        #[derive(#(#derives),*)]
+        #vis struct #patch_struct_ident {
+            #patch_struct_content
+        }
+        impl ::core::default::Default for #patch_struct_ident {
+            fn default() -> Self {
+                #patch_struct_ident {
+                    #patch_default_content
+                }
+            }
+        }
+        impl #builder_struct_ident {
+            /// Sets every field that is set in `patch`, replacing the value
+            /// set before. Fields unset in `patch` are left as they are.
+            #vis fn merge(&mut self, patch: #patch_struct_ident) -> &mut Self {
+                #merge_content
+                self
+            }
+        }
+        ))
+    } else {
+        None
+    };
     let ret = quote!(
-    pub struct #builder_struct_ident {
+    #[doc = #builder_doc]
+    #[derive(#(#derives),*)]
+    #vis struct #builder_struct_ident {
         #builder_struct_content
     }
-    impl #struct_ident {
-        pub fn builder() -> #builder_struct_ident {
+    impl ::core::default::Default for #builder_struct_ident {
+        fn default() -> Self {
             #builder_struct_ident {
                 #builder_fn_content
             }
         }
     }
+    impl #struct_ident {
+        #[doc = #builder_fn_doc]
+        #vis fn #builder_fn_ident() -> #builder_struct_ident {
+            ::core::default::Default::default()
+        }
+    }
     impl #builder_struct_ident {
+        #presets
         #builder_setters
-        pub fn build(
+        /// Returns whether every field has been set or has a fallback value,
+        /// such as a default or an environment variable that parses, which
+        /// building requires. A `validate` hook is not run.
+        #vis fn is_complete(&self) -> bool {
+            true #(&& #required_fields)*
+        }
+        #[doc = #build_doc]
+        #vis fn #build_fn_ident(
             &mut self
-        ) -> std::result::Result<#struct_ident, std::boxed::Box<dyn std::error::Error>> {
+        ) -> ::core::result::Result<#struct_ident, #error_ty> {
             #check_field_is_none
-            Ok(#struct_ident {
+            let value = #target {
                 #builder_to_struct_content
-            })
+            };
+            #validate
+            ::core::result::Result::Ok(value)
         }
     }
+    #from_struct
+    #patch
     );
     Ok(ret)
 }
 
-fn get_struct_fields(
-    st: &DeriveInput,
-) -> syn::Result<&syn::punctuated::Punctuated<syn::Field, syn::Token![,]>> {
-    if let syn::Data::Struct(syn::DataStruct {
-        fields: syn::Fields::Named(syn::FieldsNamed { ref named, .. }),
-        ..
-    }) = st.data
+/// Returns the parameter type of a setter and the expression that turns the
+/// parameter into a value of `ty`.
+fn setter_arg(
+    ident: &syn::Ident,
+    ty: &syn::Type,
+    into: bool,
+) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+    if into {
+        (
+            quote!(impl ::core::convert::Into<#ty>),
+            quote!(::core::convert::Into::into(#ident)),
+        )
+    } else {
+        (quote!(#ty), quote!(#ident))
+    }
+}
+
+/// Returns the builder type derived for a struct type, e.g. `config::ServerBuilder`
+/// for `config::Server`.
+fn get_sub_builder_type(ty: &syn::Type) -> Option<syn::Type> {
+    if let syn::Type::Path(syn::TypePath {
+        qself: None,
+        ref path,
+    }) = ungroup(ty)
     {
-        return Ok(named);
+        let mut path = path.clone();
+        let seg = path.segments.last_mut()?;
+        seg.ident = format_ident!("{}Builder", seg.ident, span = seg.ident.span());
+        return Some(syn::Type::Path(syn::TypePath { qself: None, path }));
     }
-    Err(syn::Error::new_spanned(st, "miss field"))
+    None
 }
 
-fn get_field_inner_type(ty: &syn::Type, s: String) -> Option<&syn::Type> {
+/// Turns a `CamelCase` variant name into `snake_case`. A run of capitals is
+/// kept as one word, so `HTTPGet` becomes `http_get`.
+fn to_snake_case(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut ret = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let after_lower = i > 0 && !chars[i - 1].is_uppercase();
+            let ends_run = i > 0
+                && chars[i - 1].is_uppercase()
+                && chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if after_lower || ends_run {
+                ret.push('_');
+            }
+            ret.extend(c.to_lowercase());
+        } else {
+            ret.push(c);
+        }
+    }
+    ret
+}
+
+/// Returns `T` if `ty` is written as `Option<T>`, `std::option::Option<T>` or
+/// `core::option::Option<T>`. Other types named `Option` are not std's.
+fn get_option_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
     if let syn::Type::Path(syn::TypePath {
+        qself: None,
         path: syn::Path { ref segments, .. },
-        ..
-    }) = ty
+    }) = ungroup(ty)
     {
-        if let Some(seg) = segments.last() {
-            if seg.ident == s {
-                if let syn::PathArguments::AngleBracketed(syn::AngleBracketedGenericArguments {
-                    ref args,
-                    ..
-                }) = seg.arguments
-                {
-                    if let Some(syn::GenericArgument::Type(inner_ty)) = args.first() {
-                        return Some(inner_ty);
-                    }
-                }
+        let names: Vec<_> = segments.iter().map(|seg| seg.ident.to_string()).collect();
+        let is_option = match names.as_slice() {
+            [option] => option == "Option",
+            [krate, module, option] => {
+                (krate == "std" || krate == "core") && module == "option" && option == "Option"
+            }
+            _ => false,
+        };
+        if !is_option {
+            return None;
+        }
+        if let Some(syn::PathArguments::AngleBracketed(syn::AngleBracketedGenericArguments {
+            ref args,
+            ..
+        })) = segments.last().map(|seg| &seg.arguments)
+        {
+            if let Some(syn::GenericArgument::Type(inner_ty)) = args.first() {
+                return Some(inner_ty);
             }
         }
     }
     None
 }
 
-fn get_attr_builder_name(field: &syn::Field) -> syn::Result<Option<syn::Ident>> {
-    for attr in &field.attrs {
-        if let Ok(syn::Meta::List(syn::MetaList {
-            ref path,
-            ref nested,
-            ..
-        })) = attr.parse_meta()
-        {
-            if let Some(p) = path.segments.first() {
-                if p.ident == "builder" {
-                    if let Some(syn::NestedMeta::Meta(syn::Meta::NameValue(kv))) = nested.first() {
-                        if kv.path.is_ident("each") {
-                            if let syn::Lit::Str(ref ident_str) = kv.lit {
-                                return Ok(Some(syn::Ident::new(
-                                    ident_str.value().as_str(),
-                                    attr.span(),
-                                )));
-                            }
-                        } else {
-                            if let Ok(syn::Meta::List(ref list)) = attr.parse_meta() {
-                                return Err(syn::Error::new_spanned(
-                                    list,
-                                    r#"expected `builder(each = "...")`"#,
-                                ));
-                            }
-                        }
-                    }
+/// Returns the item types a collection is extended with by an `each` setter:
+/// the key and value types for maps, the element type for anything else.
+///
+/// Only the type is known here, not the traits it implements, so this goes by
+/// name: a type whose name ends with `Map` and that has two type arguments is
+/// taken to be a map, any other type with a type argument is taken to hold
+/// items of its first one. A type that is not a collection after all, such as
+/// `Box<T>`, is only reported by the compiler for lacking `Extend`.
+fn get_collection_item_types(ty: &syn::Type) -> Option<Vec<&syn::Type>> {
+    if let syn::Type::Path(syn::TypePath {
+        path: syn::Path { ref segments, .. },
+        ..
+    }) = ungroup(ty)
+    {
+        if let Some(seg) = segments.last() {
+            if let syn::PathArguments::AngleBracketed(syn::AngleBracketedGenericArguments {
+                ref args,
+                ..
+            }) = seg.arguments
+            {
+                let tys: Vec<_> = args
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        syn::GenericArgument::Type(ty) => Some(ty),
+                        _ => None,
+                    })
+                    .collect();
+                let len = if seg.ident.to_string().ends_with("Map") && tys.len() >= 2 {
+                    2
+                } else {
+                    1
+                };
+                if !tys.is_empty() {
+                    return Some(tys[..len].to_vec());
                 }
             }
         }
     }
-    Ok(None)
+    None
+}
+
+/// Looks through the invisible group around a type passed to a
+/// `macro_rules!` macro as `$t:ty`.
+fn ungroup(ty: &syn::Type) -> &syn::Type {
+    match ty {
+        syn::Type::Group(group) => ungroup(&group.elem),
+        _ => ty,
+    }
 }
@@ -0,0 +1,50 @@
+// Passing `"cargo".to_owned()` to every String setter gets tedious. With
+// #[builder(setter(into))] the setter accepts anything that converts into the
+// field type. On the struct it becomes the default for every field, and a
+// field can opt back out with #[builder(setter(into = false))].
+//
+// Optional fields get a setter taking the inner type by default. With
+// #[builder(setter(strip_option = false))] the setter keeps the Option<T>
+// signature so that None can be passed explicitly.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(setter(into))]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    #[builder(setter(into = false))]
+    env: Vec<String>,
+    current_dir: Option<String>,
+    #[builder(setter(strip_option = false))]
+    timeout: Option<u64>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo")
+        .arg("build")
+        .arg(String::from("--release"))
+        .env(vec![])
+        .current_dir("..")
+        .timeout(None)
+        .build()
+        .unwrap();
+
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build", "--release"]);
+    assert_eq!(command.current_dir.as_deref(), Some(".."));
+    assert_eq!(command.timeout, None);
+
+    let command = Command::builder()
+        .executable("cargo")
+        .env(vec![])
+        .timeout(Some(30))
+        .build()
+        .unwrap();
+
+    assert!(command.args.is_empty());
+    assert_eq!(command.timeout, Some(30));
+}
@@ -0,0 +1,42 @@
+// Setters are named after their field by default. A field-level
+// #[builder(setter(name = "..."))] picks a different name, and a struct-level
+// #[builder(setter(prefix = "..."))] prepends a prefix to every setter that is
+// not explicitly named. This keeps fields such as `build` from clashing with
+// the methods of the builder itself.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(setter(prefix = "with_"))]
+pub struct Job {
+    build: String,
+    #[builder(setter(name = "timeout_secs"))]
+    timeout: u64,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    retries: Option<u32>,
+}
+
+fn main() {
+    let job = Job::builder()
+        .with_build("cargo build".to_owned())
+        .timeout_secs(30)
+        .arg("--release".to_owned())
+        .with_retries(3)
+        .build()
+        .unwrap();
+
+    assert_eq!(job.build, "cargo build");
+    assert_eq!(job.timeout, 30);
+    assert_eq!(job.args, vec!["--release"]);
+    assert_eq!(job.retries, Some(3));
+
+    let job = Job::builder()
+        .with_build("cargo test".to_owned())
+        .timeout_secs(60)
+        .with_args(vec!["--all".to_owned()])
+        .build()
+        .unwrap();
+
+    assert_eq!(job.args, vec!["--all"]);
+}
@@ -0,0 +1,43 @@
+// Some invariants span several fields and can only be checked once the whole
+// struct is assembled. A struct-level #[builder(validate = "path::to::fn")]
+// names a function taking the built struct by reference and returning
+// Result<(), String>. The function runs at the end of build(), and an Err is
+// returned from build() as the builder error.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(validate = "checks::validate_ports")]
+pub struct Server {
+    host: String,
+    port: u16,
+    admin_port: Option<u16>,
+}
+
+mod checks {
+    pub fn validate_ports(server: &super::Server) -> Result<(), String> {
+        if server.admin_port == Some(server.port) {
+            return Err(format!("admin_port must differ from port {}", server.port));
+        }
+        Ok(())
+    }
+}
+
+fn main() {
+    let server = Server::builder()
+        .host("localhost".to_owned())
+        .port(8080)
+        .admin_port(8081)
+        .build()
+        .unwrap();
+    assert_eq!(server.host, "localhost");
+
+    let err = Server::builder()
+        .host("localhost".to_owned())
+        .port(8080)
+        .admin_port(8080)
+        .build()
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "admin_port must differ from port 8080");
+}
@@ -0,0 +1,64 @@
+// The one-at-a-time setter generated by #[builder(each = "...")] is not limited
+// to Vec. It works with any collection that implements Default and Extend, so
+// sets and maps can be filled one item at a time too. For maps, whose items
+// are (key, value) pairs, the setter takes the key and the value as two
+// parameters. Types are told to be maps by name and two type arguments, so
+// a collection named like a map with a single type argument, such as BitMap
+// below, is filled one item at a time like a Vec. Neither can be told for an
+// alias without type arguments, whose setter takes a single item as the
+// collection iterates over it, e.g. a (key, value) tuple for a map.
+
+use derive_builder::Builder;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+pub type Env = HashMap<String, String>;
+
+#[derive(Clone, Default)]
+pub struct BitMap<T>(BTreeSet<T>);
+
+impl<T: Ord> Extend<T> for BitMap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.0.extend(iter);
+    }
+}
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: VecDeque<String>,
+    #[builder(each = "env")]
+    env: HashMap<String, String>,
+    #[builder(each = "feature")]
+    features: HashSet<String>,
+    #[builder(each = "limit")]
+    limits: BTreeMap<String, u64>,
+    #[builder(each = "cpu")]
+    cpus: BitMap<u8>,
+    #[builder(each = "var")]
+    vars: Env,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .arg("--release".to_owned())
+        .env("RUST_LOG".to_owned(), "debug".to_owned())
+        .env("RUST_BACKTRACE".to_owned(), "1".to_owned())
+        .feature("serde".to_owned())
+        .feature("serde".to_owned())
+        .cpu(3)
+        .cpu(1)
+        .var(("HOME".to_owned(), "/root".to_owned()))
+        .build()
+        .unwrap();
+
+    assert_eq!(command.args, vec!["build", "--release"]);
+    assert_eq!(command.env.len(), 2);
+    assert_eq!(command.env["RUST_LOG"], "debug");
+    assert_eq!(command.features.len(), 1);
+    assert!(command.limits.is_empty());
+    assert_eq!(command.vars["HOME"], "/root");
+    assert_eq!(command.cpus.0.into_iter().collect::<Vec<_>>(), vec![1, 3]);
+}
@@ -0,0 +1,37 @@
+// Tuple structs get a builder too. Their fields have no names, so the builder
+// calls them by position, `_0`, `_1` and so on, unless a field is given a name
+// with #[builder(name = "...")]. All the other field attributes work the same
+// as on structs with named fields.
+//
+// A unit struct has nothing to set, and its builder simply builds it.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Endpoint(String, #[builder(name = "port")] u16, Option<String>);
+
+#[derive(Builder)]
+pub struct Args(#[builder(each = "arg")] Vec<String>);
+
+#[derive(Builder)]
+pub struct Marker;
+
+fn main() {
+    let endpoint = Endpoint::builder()
+        ._0("localhost".to_owned())
+        .port(8080)
+        .build()
+        .unwrap();
+
+    assert_eq!(endpoint.0, "localhost");
+    assert_eq!(endpoint.1, 8080);
+    assert_eq!(endpoint.2, None);
+
+    let err = Endpoint::builder().port(8080).build().err().unwrap();
+    assert_eq!(err.to_string(), "_0 is None");
+
+    let args = Args::builder().arg("build".to_owned()).build().unwrap();
+    assert_eq!(args.0, vec!["build"]);
+
+    let Marker = Marker::builder().build().unwrap();
+}
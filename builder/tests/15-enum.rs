@@ -0,0 +1,66 @@
+// Deriving Builder on an enum generates one builder per variant. The builder
+// of variant `Circle` is created by `Shape::circle_builder()`, is named
+// `ShapeCircleBuilder`, and its build() returns the enum. Fields of a variant
+// support the same attributes and optional fields as the fields of a struct.
+// A run of capitals in a variant name counts as one word, so the builder of
+// `HTTPGet` is created by `http_get_builder()`.
+
+use derive_builder::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(setter(into))]
+pub enum Message {
+    Text {
+        body: String,
+        reply_to: Option<u64>,
+    },
+    Broadcast {
+        body: String,
+        #[builder(each = "recipient")]
+        recipients: Vec<String>,
+    },
+    Ack(u64),
+    HTTPGet {
+        url: String,
+    },
+    Ping,
+}
+
+fn main() {
+    let text = Message::text_builder().body("hi").build().unwrap();
+    assert_eq!(
+        text,
+        Message::Text {
+            body: "hi".to_owned(),
+            reply_to: None,
+        }
+    );
+
+    let broadcast: Message = Message::broadcast_builder()
+        .body("hello all")
+        .recipient("alice")
+        .recipient("bob")
+        .build()
+        .unwrap();
+    assert_eq!(
+        broadcast,
+        Message::Broadcast {
+            body: "hello all".to_owned(),
+            recipients: vec!["alice".to_owned(), "bob".to_owned()],
+        }
+    );
+
+    let mut builder: MessageAckBuilder = Message::ack_builder();
+    assert!(builder.build().is_err());
+    assert_eq!(builder._0(7u64).build().unwrap(), Message::Ack(7));
+
+    let get = Message::http_get_builder().url("/").build().unwrap();
+    assert_eq!(
+        get,
+        Message::HTTPGet {
+            url: "/".to_owned()
+        }
+    );
+
+    assert_eq!(Message::ping_builder().build().unwrap(), Message::Ping);
+}
@@ -0,0 +1,21 @@
+// All malformed #[builder(...)] attributes are reported in one go, instead of
+// only the first one, so that fixing them doesn't take one compile per error.
+// Misspelled keys come with a suggestion of the key that was probably meant.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(setter(int), valiate = "check")]
+pub struct Command {
+    executable: String,
+    #[builder(eac = "arg")]
+    args: Vec<String>,
+    #[builder(each = "env", setter(strip_option = "no"))]
+    env: Vec<String>,
+    #[builder(each = "dir")]
+    current_dir: Option<&'static str>,
+    #[builder(color = "red")]
+    color: String,
+}
+
+fn main() {}
@@ -0,0 +1,45 @@
+// A field marked #[builder(default)] no longer needs to be set: build() falls
+// back to Default::default() for it. An arbitrary expression can be given as
+// well, as in #[builder(default = 8080)]. On an optional field the expression
+// has the type of the field, so it is an Option too.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Server {
+    host: String,
+    #[builder(default = 8000 + 80)]
+    port: u16,
+    #[builder(default)]
+    verbose: bool,
+    #[builder(default = Some("/var/log".to_owned()))]
+    log_dir: Option<String>,
+    #[builder(each = "header", default = vec!["Server: test".to_owned()])]
+    headers: Vec<String>,
+}
+
+fn main() {
+    let server = Server::builder()
+        .host("localhost".to_owned())
+        .build()
+        .unwrap();
+
+    assert_eq!(server.port, 8080);
+    assert!(!server.verbose);
+    assert_eq!(server.log_dir.as_deref(), Some("/var/log"));
+    assert_eq!(server.headers, vec!["Server: test"]);
+
+    let server = Server::builder()
+        .host("localhost".to_owned())
+        .port(9000)
+        .verbose(true)
+        .log_dir("/tmp".to_owned())
+        .header("Connection: close".to_owned())
+        .build()
+        .unwrap();
+
+    assert_eq!(server.port, 9000);
+    assert!(server.verbose);
+    assert_eq!(server.log_dir.as_deref(), Some("/tmp"));
+    assert_eq!(server.headers, vec!["Connection: close"]);
+}
@@ -0,0 +1,41 @@
+// An existing value can be turned back into a builder, either by value through
+// `From<Command> for CommandBuilder` or by reference through
+// `Command::to_builder()`. Every field of the builder is prefilled from the
+// value, including `each` collections, so changing a couple of fields and
+// building again yields a modified copy.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .current_dir("..".to_owned())
+        .build()
+        .unwrap();
+
+    let release = command
+        .to_builder()
+        .arg("--release".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(release.executable, "cargo");
+    assert_eq!(release.args, vec!["build", "--release"]);
+    assert_eq!(release.current_dir.as_deref(), Some(".."));
+
+    let test = CommandBuilder::from(command)
+        .args(vec!["test".to_owned()])
+        .build()
+        .unwrap();
+    assert_eq!(test.executable, "cargo");
+    assert_eq!(test.args, vec!["test"]);
+    assert_eq!(test.current_dir.as_deref(), Some(".."));
+}
@@ -0,0 +1,42 @@
+// The builder, its setters and build() are as visible as the struct being
+// built, so that a builder derived in a library module can be used from
+// outside of it. #[builder(vis = "...")] overrides the visibility of the whole
+// builder on the struct, and of a single setter on a field.
+
+mod config {
+    use derive_builder::Builder;
+
+    #[derive(Builder)]
+    pub struct Server {
+        pub host: String,
+        pub port: u16,
+        #[builder(vis = "pub(crate)")]
+        pub workers: Option<usize>,
+        #[builder(vis = "", default)]
+        pub secret: String,
+    }
+
+    #[derive(Builder)]
+    #[builder(vis = "pub(crate)")]
+    pub struct Limits {
+        pub max_connections: usize,
+    }
+}
+
+fn main() {
+    let server = config::Server::builder()
+        .host("localhost".to_owned())
+        .port(8080)
+        .workers(4)
+        .build()
+        .unwrap();
+    assert_eq!(server.port, 8080);
+    assert_eq!(server.workers, Some(4));
+    assert_eq!(server.secret, "");
+
+    let limits = config::Limits::builder()
+        .max_connections(64)
+        .build()
+        .unwrap();
+    assert_eq!(limits.max_connections, 64);
+}
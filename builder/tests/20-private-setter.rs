@@ -0,0 +1,20 @@
+// A setter given a narrower visibility with #[builder(vis = "...")] cannot be
+// called from outside of the module defining the struct.
+
+mod config {
+    use derive_builder::Builder;
+
+    #[derive(Builder)]
+    pub struct Server {
+        pub host: String,
+        #[builder(vis = "", default)]
+        pub secret: String,
+    }
+}
+
+fn main() {
+    let _ = config::Server::builder()
+        .host("localhost".to_owned())
+        .secret("hunter2".to_owned())
+        .build();
+}
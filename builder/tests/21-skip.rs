@@ -0,0 +1,30 @@
+// Some fields, like caches or internal counters, must never be set through the
+// builder. A field marked #[builder(skip)] has no setter and is initialized with
+// Default::default() by build(), while #[builder(skip = "...")] initializes it
+// with the given expression instead.
+
+use derive_builder::Builder;
+use std::collections::HashMap;
+
+#[derive(Builder)]
+pub struct Resolver {
+    upstream: String,
+    #[builder(skip)]
+    cache: HashMap<String, String>,
+    #[builder(skip = "1")]
+    generation: u64,
+}
+
+fn main() {
+    let resolver = Resolver::builder()
+        .upstream("1.1.1.1".to_owned())
+        .build()
+        .unwrap();
+
+    assert_eq!(resolver.upstream, "1.1.1.1");
+    assert!(resolver.cache.is_empty());
+    assert_eq!(resolver.generation, 1);
+
+    let resolver = resolver.to_builder().build().unwrap();
+    assert_eq!(resolver.generation, 1);
+}
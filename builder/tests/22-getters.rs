@@ -0,0 +1,37 @@
+// With #[builder(getters)] the builder gets a `get_` accessor for every field,
+// returning what has been set so far, if anything. The plain field name is
+// already taken by the setter.
+//
+// Every builder also reports through is_complete() whether all fields that
+// build() requires have been set. Optional fields, `each` collections and
+// fields with a default don't need to be set.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(getters)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+    #[builder(default)]
+    verbose: bool,
+}
+
+fn main() {
+    let mut builder = Command::builder();
+    assert_eq!(builder.get_executable(), None);
+    assert_eq!(builder.get_args(), None);
+    assert!(!builder.is_complete());
+
+    builder.executable("cargo".to_owned()).arg("build".to_owned());
+    assert_eq!(builder.get_executable().map(String::as_str), Some("cargo"));
+    assert_eq!(builder.get_args(), Some(&vec!["build".to_owned()]));
+    assert_eq!(builder.get_current_dir(), None);
+    assert_eq!(builder.get_verbose(), None);
+    assert!(builder.is_complete());
+
+    let command = builder.build().unwrap();
+    assert_eq!(command.executable, "cargo");
+}
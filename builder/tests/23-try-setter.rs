@@ -0,0 +1,36 @@
+// With #[builder(try_setter)] a field gets a fallible `try_` setter next to
+// its normal setter. It accepts anything that converts into the field type
+// through TryInto and returns the conversion error, if any. On the struct it
+// applies to every field, and a field can opt out with
+// #[builder(try_setter = false)].
+
+use derive_builder::Builder;
+use std::net::Ipv4Addr;
+
+#[derive(Builder)]
+pub struct Listener {
+    #[builder(try_setter)]
+    port: u16,
+    #[builder(try_setter)]
+    backlog: Option<u8>,
+    addr: Ipv4Addr,
+}
+
+fn main() {
+    let listener = Listener::builder()
+        .try_port(8080u32)
+        .unwrap()
+        .try_backlog(128i64)
+        .unwrap()
+        .addr(Ipv4Addr::LOCALHOST)
+        .build()
+        .unwrap();
+
+    assert_eq!(listener.port, 8080);
+    assert_eq!(listener.backlog, Some(128));
+
+    let mut builder = Listener::builder();
+    assert!(builder.try_port(70000u32).is_err());
+    assert!(builder.try_backlog(-1i64).is_err());
+    assert!(!builder.is_complete());
+}
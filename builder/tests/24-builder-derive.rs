@@ -0,0 +1,29 @@
+// The builder implements Default, giving the same empty builder as
+// Command::builder(). Further traits can be derived on the builder with
+// #[builder(derive(...))], for example to clone a partially configured
+// template builder or to print it while debugging.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(derive(Clone, Debug))]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+}
+
+fn main() {
+    let mut template = CommandBuilder::default();
+    template.executable("cargo".to_owned());
+
+    let build = template.clone().arg("build".to_owned()).build().unwrap();
+    let test = template.clone().arg("test".to_owned()).build().unwrap();
+    assert_eq!(build.args, vec!["build"]);
+    assert_eq!(test.args, vec!["test"]);
+
+    let debug = format!("{:?}", template);
+    assert!(debug.starts_with("CommandBuilder"));
+    assert!(debug.contains("cargo"));
+}
@@ -0,0 +1,60 @@
+// With #[builder(no_std)] the generated code only refers to `core` and `alloc`,
+// so builders can be derived in crates without the standard library. The
+// crate needs `extern crate alloc;`, which provides the error type of build():
+// alloc::boxed::Box<dyn core::error::Error>.
+//
+// Below, the empty `std` module shadows the standard library inside of the
+// `firmware` module, so any `std::` path in the generated code would fail to
+// resolve.
+
+extern crate alloc;
+
+mod firmware {
+    mod std {}
+
+    use alloc::string::String;
+    use alloc::vec::Vec;
+    use derive_builder::Builder;
+
+    #[derive(Builder)]
+    #[builder(no_std, derive(Clone, Debug), validate = "check_baud")]
+    pub struct UartConfig {
+        pub name: String,
+        pub baud: u32,
+        #[builder(each = "pin")]
+        pub pins: Vec<u8>,
+        pub parity: Option<bool>,
+        #[builder(default = 1)]
+        pub stop_bits: u8,
+    }
+
+    fn check_baud(config: &UartConfig) -> Result<(), String> {
+        if config.baud == 0 {
+            return Err(String::from("baud must not be zero"));
+        }
+        Ok(())
+    }
+}
+
+fn main() {
+    let config = firmware::UartConfig::builder()
+        .name("uart0".into())
+        .baud(115_200)
+        .pin(4)
+        .pin(5)
+        .build()
+        .unwrap();
+    assert_eq!(config.pins, [4, 5]);
+    assert_eq!(config.stop_bits, 1);
+
+    let err = firmware::UartConfig::builder().baud(9600).build().err().unwrap();
+    assert_eq!(err.to_string(), "name is None");
+
+    let err = firmware::UartConfig::builder()
+        .name("uart1".into())
+        .baud(0)
+        .build()
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "baud must not be zero");
+}
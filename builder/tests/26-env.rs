@@ -0,0 +1,57 @@
+// A field marked #[builder(env = "...")] that has not been set is read from the
+// given environment variable by build(), and parsed with FromStr. A variable
+// that fails to parse makes build() return an error. When the variable is not
+// set either, the field falls back to its default, if any, or else is missing
+// like any other unset field. is_complete() takes the variables into account
+// the same way.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Service {
+    #[builder(env = "BUILDER_TEST_HOST")]
+    host: String,
+    #[builder(env = "BUILDER_TEST_PORT", default = 8080)]
+    port: u16,
+    #[builder(env = "BUILDER_TEST_WORKERS")]
+    workers: Option<usize>,
+}
+
+fn main() {
+    std::env::remove_var("BUILDER_TEST_HOST");
+    std::env::remove_var("BUILDER_TEST_PORT");
+    std::env::remove_var("BUILDER_TEST_WORKERS");
+
+    assert!(!Service::builder().is_complete());
+    let err = Service::builder().build().err().unwrap();
+    assert_eq!(err.to_string(), "host is None");
+
+    std::env::set_var("BUILDER_TEST_HOST", "example.com");
+    assert!(Service::builder().is_complete());
+    let service = Service::builder().build().unwrap();
+    assert_eq!(service.host, "example.com");
+    assert_eq!(service.port, 8080);
+    assert_eq!(service.workers, None);
+
+    std::env::set_var("BUILDER_TEST_PORT", "9000");
+    std::env::set_var("BUILDER_TEST_WORKERS", "4");
+    let service = Service::builder()
+        .host("localhost".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(service.host, "localhost");
+    assert_eq!(service.port, 9000);
+    assert_eq!(service.workers, Some(4));
+
+    std::env::set_var("BUILDER_TEST_PORT", "http");
+    assert!(!Service::builder().is_complete());
+    let err = Service::builder().build().err().unwrap();
+    assert_eq!(
+        err.to_string(),
+        "port from environment variable BUILDER_TEST_PORT: invalid digit found in string",
+    );
+
+    assert!(Service::builder().port(1).is_complete());
+    let service = Service::builder().port(1).build().unwrap();
+    assert_eq!(service.port, 1);
+}
@@ -0,0 +1,75 @@
+// A field whose type derives Builder itself can be marked
+// #[builder(sub_builder)]. Instead of a setter taking a finished value, the
+// outer builder then exposes the inner builder through `&mut self`, and
+// build() builds it along with the outer struct. Errors of the inner builder
+// are prefixed with the path of the field, as in `server.port is None`.
+//
+// The builder type defaults to the field type with a `Builder` suffix, and can
+// be named explicitly with #[builder(sub_builder = "...")]. When the inner
+// builder's build function is not named `build`, the list form
+// #[builder(sub_builder(ty = "...", build_fn = "..."))] names it as well, each
+// key being optional.
+
+use derive_builder::Builder;
+
+mod net {
+    use derive_builder::Builder;
+
+    #[derive(Builder, Debug, PartialEq)]
+    pub struct Server {
+        pub host: String,
+        pub port: u16,
+    }
+}
+
+#[derive(Builder, Debug, PartialEq)]
+pub struct Limits {
+    max_connections: usize,
+}
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(build_fn(name = "finish"))]
+pub struct Tls {
+    cert: String,
+}
+
+#[derive(Builder)]
+pub struct Config {
+    name: String,
+    #[builder(sub_builder)]
+    server: net::Server,
+    #[builder(sub_builder = "LimitsBuilder")]
+    limits: Limits,
+    #[builder(sub_builder(build_fn = "finish"))]
+    tls: Tls,
+}
+
+fn main() {
+    let mut builder = Config::builder();
+    builder.name("proxy".to_owned());
+    builder.server().host("localhost".to_owned());
+    builder.limits().max_connections(64);
+    builder.tls().cert("proxy.pem".to_owned());
+    assert!(!builder.is_complete());
+
+    let err = builder.build().err().unwrap();
+    assert_eq!(err.to_string(), "server.port is None");
+
+    builder.server().port(8080);
+    assert!(builder.is_complete());
+    let config = builder.build().unwrap();
+    assert_eq!(config.name, "proxy");
+    assert_eq!(
+        config.server,
+        net::Server {
+            host: "localhost".to_owned(),
+            port: 8080,
+        }
+    );
+    assert_eq!(config.limits, Limits { max_connections: 64 });
+    assert_eq!(config.tls.cert, "proxy.pem");
+
+    let mut builder = config.to_builder();
+    builder.server().port(9090);
+    assert_eq!(builder.build().unwrap().server.port, 9090);
+}
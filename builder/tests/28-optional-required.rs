@@ -0,0 +1,59 @@
+// Only `Option<T>`, `std::option::Option<T>` and `core::option::Option<T>` are
+// recognized as std's Option and make a field optional. A type of some other
+// module that happens to be called Option is required like any other type.
+//
+// #[builder(optional)] marks a field as optional whose type is std's Option
+// but not written like it, such as an alias. It only applies to such types,
+// see 46-optional-non-option for others. #[builder(required)] makes an
+// Option field required, with a setter taking the Option itself.
+
+use derive_builder::Builder;
+
+mod my {
+    #[derive(Clone, Debug, PartialEq)]
+    pub enum Option<T> {
+        Nothing,
+        Just(T),
+    }
+}
+
+type MaybePort = Option<u16>;
+
+#[derive(Builder)]
+pub struct Service {
+    name: std::option::Option<String>,
+    mode: my::Option<u8>,
+    #[builder(optional)]
+    port: MaybePort,
+    #[builder(required)]
+    timeout: Option<u64>,
+}
+
+fn main() {
+    let err = Service::builder().timeout(None).build().err().unwrap();
+    assert_eq!(err.to_string(), "mode is None");
+
+    let err = Service::builder().mode(my::Option::Nothing).build().err().unwrap();
+    assert_eq!(err.to_string(), "timeout is None");
+
+    let service = Service::builder()
+        .mode(my::Option::Just(1))
+        .timeout(Some(30))
+        .build()
+        .unwrap();
+    assert_eq!(service.name, None);
+    assert_eq!(service.mode, my::Option::Just(1));
+    assert_eq!(service.port, None);
+    assert_eq!(service.timeout, Some(30));
+
+    let service = Service::builder()
+        .name("api".to_owned())
+        .mode(my::Option::Nothing)
+        .port(8080)
+        .timeout(None)
+        .build()
+        .unwrap();
+    assert_eq!(service.name.as_deref(), Some("api"));
+    assert_eq!(service.port, Some(8080));
+    assert_eq!(service.timeout, None);
+}
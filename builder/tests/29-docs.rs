@@ -0,0 +1,34 @@
+// The builder type, builder() and build() are documented, and the
+// documentation of build() lists the fields that have to be set. Doc comments
+// of the fields are repeated on their setters, so that the builder reads well
+// in rustdoc.
+//
+// Since the crate is compiled with #![deny(missing_docs)], this only compiles
+// when every public item generated for a documented struct is documented too.
+
+#![deny(missing_docs)]
+//! Documented builders.
+
+use derive_builder::Builder;
+
+/// A command to run.
+#[derive(Builder)]
+#[builder(getters, try_setter)]
+pub struct Command {
+    /// The program to run.
+    pub executable: String,
+    /// Arguments passed to the program.
+    #[builder(each = "arg")]
+    pub args: Vec<String>,
+    /// Directory to run the program in.
+    pub current_dir: Option<String>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .build()
+        .unwrap();
+    assert!(command.to_builder().is_complete());
+}
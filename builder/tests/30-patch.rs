@@ -0,0 +1,59 @@
+// With #[builder(patch)], a CommandPatch struct is generated next to the
+// builder. It has a public Option field for every field of the builder, so
+// that partial values can be collected from several sources, e.g. defaults,
+// a config file and the command line, and merged in that order before the
+// final build().
+//
+//     impl CommandBuilder {
+//         pub fn merge(&mut self, patch: CommandPatch) -> &mut Self {...}
+//     }
+//
+// Fields set in the patch replace the value in the builder, fields left as
+// None keep it.
+
+use derive_builder::Builder;
+
+#[derive(Builder, Debug)]
+#[builder(patch, derive(Debug, Clone))]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+    #[builder(default = 1)]
+    jobs: u32,
+}
+
+fn main() {
+    let defaults = CommandPatch {
+        executable: Some("cargo".to_owned()),
+        jobs: Some(4),
+        ..Default::default()
+    };
+    let config_file = CommandPatch {
+        current_dir: Some("..".to_owned()),
+        jobs: Some(8),
+        ..Default::default()
+    };
+    let command_line = CommandPatch {
+        args: Some(vec!["build".to_owned()]),
+        ..Default::default()
+    };
+
+    let command = Command::builder()
+        .merge(defaults)
+        .merge(config_file.clone())
+        .merge(command_line)
+        .build()
+        .unwrap();
+
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build"]);
+    assert_eq!(command.current_dir.unwrap(), "..");
+    assert_eq!(command.jobs, 8);
+
+    // A patch leaves fields it does not set untouched.
+    let mut builder = Command::builder();
+    builder.executable("rustc".to_owned()).merge(config_file);
+    assert_eq!(builder.build().unwrap().executable, "rustc");
+}
@@ -0,0 +1,41 @@
+// Next to the setter adding a single item, an `each` field gets a setter
+// adding all items of an iterator, named after the field with an `_extend`
+// suffix:
+//
+//     impl CommandBuilder {
+//         fn arg(&mut self, arg: String) -> &mut Self {...}
+//         fn args_extend(&mut self, args: impl IntoIterator<Item = String>) -> &mut Self {...}
+//     }
+//
+// The name can be chosen with `each(name = "...", extend = "...")`. Like the
+// single item setter, an extend setter named after the field replaces the
+// setter of the whole collection.
+
+use derive_builder::Builder;
+use std::collections::HashMap;
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    #[builder(each(name = "env", extend = "envs"))]
+    env: HashMap<String, String>,
+    #[builder(each(name = "feature", extend = "features"))]
+    features: Vec<String>,
+}
+
+fn main() {
+    let command = Command::builder()
+        .arg("build".to_owned())
+        .args_extend(vec!["--release".to_owned(), "--locked".to_owned()])
+        .env("RUST_LOG".to_owned(), "info".to_owned())
+        .envs([("RUST_BACKTRACE".to_owned(), "1".to_owned())])
+        .features(["std", "alloc"].iter().map(|s| s.to_string()))
+        .feature("serde".to_owned())
+        .build()
+        .unwrap();
+
+    assert_eq!(command.args, vec!["build", "--release", "--locked"]);
+    assert_eq!(command.env.len(), 2);
+    assert_eq!(command.features, vec!["std", "alloc", "serde"]);
+}
@@ -0,0 +1,26 @@
+// The builder type can be given a name other than `{Struct}Builder` with
+// #[builder(name = "...")], and the function finishing the builder a name
+// other than `build` with #[builder(build_fn(name = "..."))], e.g. to avoid
+// clashing with a `CommandBuilder` type that already exists in the crate.
+
+use derive_builder::Builder;
+
+#[allow(dead_code)]
+pub struct CommandBuilder;
+
+#[derive(Builder)]
+#[builder(name = "CommandSpec", build_fn(name = "finish"))]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+}
+
+fn main() {
+    let mut spec: CommandSpec = Command::builder();
+    spec.executable("cargo".to_owned()).arg("build".to_owned());
+    let command: Command = spec.finish().unwrap();
+
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build"]);
+}
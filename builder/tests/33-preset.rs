@@ -0,0 +1,50 @@
+// #[builder(preset(name = "...", fields(field = value, ...)))] generates a
+// constructor of the builder with the given fields already set, as an
+// alternative to the empty builder returned by builder(). The fields are
+// named as in the builder, and set values can still be overridden by the
+// setters. Values are converted with Into for fields whose setter does.
+//
+//     impl ProfileBuilder {
+//         pub fn release() -> Self {...}
+//     }
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(
+    preset(name = "dev", fields(opt_level = 0, debug = true)),
+    preset(name = "release", fields(opt_level = 3, debug = false, lto = "fat".to_owned())),
+)]
+pub struct Profile {
+    opt_level: u8,
+    debug: bool,
+    lto: Option<String>,
+    #[builder(each = "feature")]
+    features: Vec<String>,
+}
+
+#[derive(Builder)]
+#[builder(setter(into), preset(name = "cargo", fields(executable = "cargo")))]
+pub struct Command {
+    executable: String,
+}
+
+fn main() {
+    let dev = ProfileBuilder::dev().build().unwrap();
+    assert_eq!(dev.opt_level, 0);
+    assert!(dev.debug);
+    assert_eq!(dev.lto, None);
+
+    let release = ProfileBuilder::release()
+        .debug(true)
+        .feature("simd".to_owned())
+        .build()
+        .unwrap();
+    assert_eq!(release.opt_level, 3);
+    assert!(release.debug);
+    assert_eq!(release.lto.unwrap(), "fat");
+    assert_eq!(release.features, vec!["simd"]);
+
+    let command = CommandBuilder::cargo().build().unwrap();
+    assert_eq!(command.executable, "cargo");
+}
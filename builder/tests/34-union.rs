@@ -0,0 +1,13 @@
+// Builder can be derived for structs and enums, but there is no way to build
+// a union from a set of fields as only one of them is ever set. Deriving it
+// for a union is an error pointing at the union.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub union Value {
+    int: i64,
+    float: f64,
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+// Setter names are derived from field names but can be chosen freely with
+// setter(name = ...), setter(prefix = ...), each and getters, so two fields
+// may end up with methods of the same name. Rather than leaving rustc to
+// report a duplicate definition inside the generated impl, the conflicting
+// setter is reported where its name comes from. The same goes for setters
+// named like the other methods of the builder, such as build().
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    #[builder(setter(name = "executable"))]
+    program: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    #[builder(each = "arg")]
+    extra_args: Vec<String>,
+    build: String,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+// The setter generated by `each` extends the field with one item at a time,
+// so the field has to be a collection type, named by a path such as Vec<T>,
+// HashMap<K, V> or an alias. Other types, such as tuples and arrays, are
+// reported at the type of the field.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder(each = "arg")]
+    args: (String, String),
+    #[builder(each = "env")]
+    env: [u32; 4],
+}
+
+fn main() {}
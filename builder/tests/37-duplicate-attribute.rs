@@ -0,0 +1,17 @@
+// An attribute given twice, whether inside the same #[builder(...)] or in
+// two of them, is an error instead of the last one silently winning. Only
+// `derive` and `preset` add to each other and may be repeated.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(vis = "pub", derive(Debug), derive(Clone), vis = "pub(crate)")]
+pub struct Command {
+    #[builder(each = "arg")]
+    #[builder(each = "argument")]
+    args: Vec<String>,
+    #[builder(setter(into, into), default)]
+    current_dir: String,
+}
+
+fn main() {}
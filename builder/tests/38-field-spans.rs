@@ -0,0 +1,48 @@
+// The trait calls generated for a field are spanned at the type of the field,
+// so that when the type lacks a trait the builder relies on, the error
+// underlines the offending field rather than the #[derive(Builder)]:
+//
+// - Clone, for build() and to_builder();
+// - Default and Extend, for the setters of an `each` field;
+// - FromStr, for reading an `env` field;
+// - Default, for a bare `skip`;
+// - any trait of builder(derive(...)), for the field of the builder.
+
+use derive_builder::Builder;
+
+pub struct Handle;
+
+#[derive(Clone)]
+pub struct Port;
+
+#[derive(Builder)]
+pub struct Command {
+    executable: String,
+    handle: Handle,
+}
+
+#[derive(Builder)]
+pub struct Args {
+    #[builder(each = "arg")]
+    args: Box<String>,
+}
+
+#[derive(Builder)]
+pub struct Service {
+    #[builder(env = "SERVICE_PORT")]
+    port: Port,
+}
+
+#[derive(Builder)]
+pub struct Resolver {
+    #[builder(skip)]
+    handle: Handle,
+}
+
+#[derive(Builder)]
+#[builder(derive(Debug))]
+pub struct Listener {
+    port: Port,
+}
+
+fn main() {}
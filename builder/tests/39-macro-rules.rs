@@ -0,0 +1,42 @@
+// The struct deriving Builder may come out of a macro_rules! macro, with
+// some fields written by the macro and others passed in by its caller. The
+// generated code has to resolve regardless of which macro context the types
+// and names of the fields were written in.
+
+use derive_builder::Builder;
+
+macro_rules! with_extra_fields {
+    (#[$m:meta] pub struct $n:ident { $($f:ident : $t:ty),* $(,)? }) => {
+        #[$m]
+        pub struct $n {
+            $($f: $t,)*
+            y: String,
+            #[builder(each = "z")]
+            zs: Vec<u8>,
+        }
+    };
+}
+
+with_extra_fields! {
+    #[derive(Builder)]
+    pub struct Command {
+        executable: String,
+        current_dir: Option<String>,
+    }
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .y("y".to_owned())
+        .z(1)
+        .zs_extend(vec![2, 3])
+        .build()
+        .unwrap();
+
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.current_dir, None);
+    assert_eq!(command.y, "y");
+    assert_eq!(command.zs, vec![1, 2, 3]);
+    assert!(command.to_builder().is_complete());
+}
@@ -0,0 +1,14 @@
+// Builder options are given on the enum, applying to every variant, and on
+// the fields of the variants. A #[builder(...)] on a variant itself is an
+// error, rather than being silently ignored.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub enum Request {
+    #[builder(eac = "x", whatever)]
+    Get { url: String },
+    Head { url: String },
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+// A skipped field has neither a builder field nor a setter, so no other
+// builder option has any effect on it. Combining `skip` with any of them is
+// an error listing the options that would be ignored.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Resolver {
+    upstream: String,
+    #[builder(skip, env = "RESOLVER_CACHE", setter(into))]
+    cache: Vec<String>,
+    #[builder(skip = 1, vis = "pub", try_setter, required)]
+    generation: u64,
+}
+
+fn main() {}
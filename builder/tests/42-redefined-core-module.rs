@@ -0,0 +1,40 @@
+// Like the prelude names in 09-redefined-prelude-types, the names `core`,
+// `std` and `alloc` may mean something else in the caller's code, such as a
+// module of the crate named `core`. Paths in the generated code start with
+// `::` so that they always refer to the crates of the standard library.
+
+use derive_builder::Builder;
+
+#[allow(dead_code)]
+mod core {
+    pub fn helper() {}
+}
+
+#[allow(dead_code)]
+mod std {}
+
+#[derive(Builder)]
+#[builder(try_setter)]
+pub struct Command {
+    executable: String,
+    #[builder(each = "arg")]
+    args: Vec<String>,
+    current_dir: Option<String>,
+    #[builder(default)]
+    jobs: u32,
+}
+
+fn main() {
+    let command = Command::builder()
+        .executable("cargo".to_owned())
+        .arg("build".to_owned())
+        .try_jobs(4u64)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    assert_eq!(command.executable, "cargo");
+    assert_eq!(command.args, vec!["build"]);
+    assert_eq!(command.current_dir, None);
+    assert_eq!(command.jobs, 4);
+}
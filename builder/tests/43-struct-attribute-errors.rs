@@ -0,0 +1,32 @@
+// Options of the struct-level #[builder(...)] that are given in the wrong
+// place or incompletely are reported at the option:
+//
+// - `setter(name = ...)` renames the setter of one field, so it is only
+//   allowed on fields;
+// - `preset(...)` needs a `name` for its constructor;
+// - `name` and `preset` apply to the single builder of a struct, so they are
+//   not allowed on an enum, which gets a builder per variant;
+// - `derive(Default)` would conflict with the Default impl that the builder
+//   always has.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(setter(name = "exe"), preset(fields(executable = "cargo")))]
+pub struct Command {
+    executable: String,
+}
+
+#[derive(Builder)]
+#[builder(name = "RequestSpec", preset(name = "get", fields(url = "/".to_owned())))]
+pub enum Request {
+    Get { url: String },
+}
+
+#[derive(Builder)]
+#[builder(patch, derive(Debug, Default))]
+pub struct Service {
+    host: String,
+}
+
+fn main() {}
@@ -0,0 +1,42 @@
+// Field-level options that cannot apply to the field they are given on are
+// reported at the option or at the field:
+//
+// - `name` names the builder field of a tuple struct field, a named field
+//   has its name already;
+// - `each(...)` needs a `name` for the setter adding one item;
+// - a field is either `optional` or `required`;
+// - `sub_builder` needs a non-optional field of a type deriving Builder;
+// - `env` reads std::env, which is not available with `no_std`;
+// - `env` parses a single value, which cannot fill an `each` collection.
+
+extern crate alloc;
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Server {
+    host: String,
+}
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder(name = "exe")]
+    executable: String,
+    #[builder(each(extend = "args"))]
+    args: Vec<String>,
+    #[builder(optional, required)]
+    current_dir: Option<String>,
+    #[builder(sub_builder)]
+    server: Option<Server>,
+    #[builder(each = "feature", env = "COMMAND_FEATURES")]
+    features: Vec<String>,
+}
+
+#[derive(Builder)]
+#[builder(no_std)]
+pub struct Service {
+    #[builder(env = "SERVICE_HOST")]
+    host: String,
+}
+
+fn main() {}
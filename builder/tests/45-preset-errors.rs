@@ -0,0 +1,17 @@
+// A preset can only set fields that the builder has, and its constructor
+// cannot be named like another method of the builder.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+#[builder(
+    preset(name = "release", fields(opt_level = 3, lto = true)),
+    preset(name = "build", fields(opt_level = 0)),
+)]
+pub struct Profile {
+    opt_level: u8,
+    #[builder(skip)]
+    debug: bool,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+// #[builder(optional)] only applies to types that are std's Option under
+// another name, such as an alias. Marking a field of any other type optional
+// is an error at the type of the field, where the type is expected to be an
+// Option of its items.
+
+use derive_builder::Builder;
+
+#[derive(Builder)]
+pub struct Command {
+    #[builder(optional)]
+    stdin: Vec<u8>,
+}
+
+fn main() {}
@@ -10,4 +10,41 @@ fn tests() {
     t.pass("tests/07-repeated-field.rs");
     t.compile_fail("tests/08-unrecognized-attribute.rs");
     t.pass("tests/09-redefined-prelude-types.rs");
+    t.pass("tests/10-setter-into.rs");
+    t.pass("tests/11-setter-name.rs");
+    t.pass("tests/12-validate.rs");
+    t.pass("tests/13-each-collections.rs");
+    t.pass("tests/14-tuple-and-unit-struct.rs");
+    t.pass("tests/15-enum.rs");
+    t.compile_fail("tests/16-multiple-attribute-errors.rs");
+    t.pass("tests/17-default.rs");
+    t.pass("tests/18-to-builder.rs");
+    t.pass("tests/19-visibility.rs");
+    t.compile_fail("tests/20-private-setter.rs");
+    t.pass("tests/21-skip.rs");
+    t.pass("tests/22-getters.rs");
+    t.pass("tests/23-try-setter.rs");
+    t.pass("tests/24-builder-derive.rs");
+    t.pass("tests/25-no-std.rs");
+    t.pass("tests/26-env.rs");
+    t.pass("tests/27-sub-builder.rs");
+    t.pass("tests/28-optional-required.rs");
+    t.pass("tests/29-docs.rs");
+    t.pass("tests/30-patch.rs");
+    t.pass("tests/31-each-extend.rs");
+    t.pass("tests/32-builder-name.rs");
+    t.pass("tests/33-preset.rs");
+    t.compile_fail("tests/34-union.rs");
+    t.compile_fail("tests/35-setter-conflict.rs");
+    t.compile_fail("tests/36-each-non-collection.rs");
+    t.compile_fail("tests/37-duplicate-attribute.rs");
+    t.compile_fail("tests/38-field-spans.rs");
+    t.pass("tests/39-macro-rules.rs");
+    t.compile_fail("tests/40-variant-attribute.rs");
+    t.compile_fail("tests/41-skip-conflict.rs");
+    t.pass("tests/42-redefined-core-module.rs");
+    t.compile_fail("tests/43-struct-attribute-errors.rs");
+    t.compile_fail("tests/44-field-attribute-errors.rs");
+    t.compile_fail("tests/45-preset-errors.rs");
+    t.compile_fail("tests/46-optional-non-option.rs");
 }